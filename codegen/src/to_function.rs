@@ -1,57 +1,175 @@
 use proc_macro::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, ItemFn, PatType, Type};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, ItemFn, PatType, Type};
+
+/// 将 `snake_case` 形式的函数名转换为 `PascalCase`，用于生成包装类型的名字。
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// 参数类型是否是 `Option<T>`：这类参数的 `Nil` 实参读作 `None` 而不是
+/// 触发类型错误；位于查询实参尾部连续的 `Option<T>` 参数还允许缺省实参，
+/// 而不是触发实参个数错误。
+fn is_option_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "Option";
+        }
+    }
+    false
+}
+
+/// 取出参数名，用于在类型不匹配时点名是哪个参数出了问题。
+fn pat_name(typed: &PatType) -> String {
+    let pat = &typed.pat;
+    quote! {#pat}.to_string()
+}
+
+/// 一个形参：区分引用类型（由调用方注入的上下文，例如 `&Instance`）
+/// 和查询实参（从 `args` 按声明类型取出）。
+struct Param<'a> {
+    typed: &'a PatType,
+    ty: &'a Type,
+    is_ref: bool,
+}
 
 pub fn impl_function(args: TokenStream, input: ItemFn) -> TokenStream {
     let name = &input.sig.ident;
+    let name_str = quote! {#name}.to_string();
+    let struct_name = Ident::new(&format!("{}Function", pascal_case(&name_str)), name.span());
 
-    // 获取函数调用参数
-    let args_body: Vec<proc_macro2::TokenStream> = input
+    // 按声明顺序收集全部参数。引用类型的参数（例如 `&Instance`）由调用方
+    // 注入的上下文提供，不计入数据源查询时传入的实参个数，但调用 `#name`
+    // 时仍然需要按原始顺序把它传回去。
+    let params: Vec<Param> = input
         .sig
         .inputs
         .iter()
         .map(|arg| match arg {
             syn::FnArg::Receiver(_) => unimplemented!(),
-            syn::FnArg::Typed(typed) => {
-                let pat = &typed.pat;
-                let type_ = &typed.ty;
-                let ident = quote! {#pat}.to_string();
-
-                (type_, ident, typed)
-            }
+            syn::FnArg::Typed(typed) => match &*typed.ty {
+                Type::Reference(_) => Param {
+                    typed,
+                    ty: &*typed.ty,
+                    is_ref: true,
+                },
+                _ => Param {
+                    typed,
+                    ty: &*typed.ty,
+                    is_ref: false,
+                },
+            },
         })
-        .enumerate()
-        .filter(|(_, arg)| {
-            let type_ = arg.0;
-            if let syn::Type::Reference(_) = **type_ {
-                false
-            } else {
-                true
+        .collect();
+
+    let query_params: Vec<&Param> = params.iter().filter(|param| !param.is_ref).collect();
+
+    let total = query_params.len();
+    // 从尾部开始数出连续的 `Option<T>` 参数，它们对应可以省略的尾部实参
+    let trailing_optional = query_params
+        .iter()
+        .rev()
+        .take_while(|param| is_option_type(param.ty))
+        .count();
+    let required = total - trailing_optional;
+
+    let mut query_index = 0usize;
+    let args_body: Vec<proc_macro2::TokenStream> = params
+        .iter()
+        .map(|param| {
+            if param.is_ref {
+                return quote_spanned! {param.typed.span()=> _ctx };
             }
-        })
-        .map(|(index, arg)| {
-            let type_ = arg.0;
-            let typed = arg.2;
-            match **type_ {
-                syn::Type::Reference(_) => unimplemented!(),
-                _ => {
-                    quote_spanned! {typed.span()=>
-                        args.get::<#type_>(#index)?
+
+            let index = query_index;
+            query_index += 1;
+            let type_ = param.ty;
+            let param_name = pat_name(param.typed);
+            let get_expr = quote_spanned! {param.typed.span()=>
+                args.get::<#type_>(#index).map_err(|err| {
+                    crate::Error::invalid_type(format!(
+                        "failed to parse {} for parameter `{}`: {:?}",
+                        stringify!(#type_),
+                        #param_name,
+                        err
+                    ))
+                })?
+            };
+
+            if index >= required {
+                quote_spanned! {param.typed.span()=>
+                    if #index < args.len() {
+                        #get_expr
+                    } else {
+                        None
                     }
                 }
+            } else {
+                get_expr
             }
         })
         .collect();
 
-    let args_body = quote! { let value = #name(#(#args_body),*)?;};
+    // 实参个数校验：没有可省略的尾部参数时维持原来的精确匹配报错，
+    // 否则报告可接受的实参个数区间
+    let arity_check = if required == total {
+        quote! {
+            if args.len() != #total {
+                return Err(crate::Error::invalid_type(format!(
+                    "{} expects {} args, got {}",
+                    #name_str,
+                    #total,
+                    args.len()
+                )));
+            }
+        }
+    } else {
+        quote! {
+            if args.len() < #required || args.len() > #total {
+                return Err(crate::Error::invalid_type(format!(
+                    "{} expects {} to {} args, got {}",
+                    #name_str,
+                    #required,
+                    #total,
+                    args.len()
+                )));
+            }
+        }
+    };
 
-    // 实现 DS
-    let name_str = quote! {#name}.to_string();
+    // 实现 DS：生成一个零大小的包装类型，注册到数据源时以 `name_str` 暴露，
+    // 调用前先校验实参个数，再逐个按声明的类型从 `args` 中取出并转换；引用
+    // 类型的参数由 `_ctx` 按调用方传入的上下文原样转发。
     let function_impl = quote! {
         #input
-        
+
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        pub struct #struct_name;
+
+        impl crate::Function for #struct_name {
+            fn name(&self) -> &str {
+                #name_str
+            }
+
+            fn call(&self, _ctx: &crate::Instance, args: &crate::Args) -> crate::Result<crate::Value> {
+                #arity_check
+
+                let value = #name(#(#args_body),*)?;
+                Ok(value.into())
+            }
+        }
     };
 
-    TokenStream::from(quote!(#function_impl))
+    TokenStream::from(function_impl)
 }
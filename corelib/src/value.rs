@@ -1,8 +1,35 @@
-use crate::{DataType, Error};
-use std::{convert::TryFrom, fmt::Display, str::FromStr};
+use crate::{Error, Result};
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt::Display,
+    str::FromStr,
+};
 
 pub type Bytes = Vec<u8>;
 
+/// `Value` 对应的类型标签，用于在已知 schema 时严格解析/校验
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    /// 字符串类型
+    String,
+    /// 64 位有符号整型
+    Integer,
+    /// 64 位无符号整型
+    UInteger,
+    /// 128 位有符号整型
+    BigInteger,
+    /// 64 位有符号浮点型
+    Number,
+    /// 定点小数
+    Decimal,
+    /// Boolean 类型
+    Boolean,
+    /// 字节数组
+    Bytes,
+    /// 空值
+    Nil,
+}
+
 /// 所支持的值类型
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
@@ -10,8 +37,14 @@ pub enum Value {
     String(String),
     /// 64 位有符号整型
     Integer(i64),
+    /// 64 位无符号整型，用于承载超出 `i64` 范围的正整数
+    UInteger(u64),
+    /// 128 位有符号整型，用于承载超出 `u64` 范围的大整数
+    BigInteger(i128),
     /// 64 位有符号浮点型
     Number(f64),
+    /// 定点小数，精确表示货币等场景下的大位数十进制数据
+    Decimal(Decimal),
     /// Boolean 类型
     Boolean(bool),
     /// 字节数组
@@ -22,6 +55,132 @@ pub enum Value {
 
 impl Eq for Value {}
 
+/// 定点小数：表示 `mantissa * 10^-scale`，相比 `f64` 不会损失精度，
+/// 加法与比较都会先将两侧对齐到相同的 `scale` 再进行。
+#[derive(Debug, Clone, Copy)]
+pub struct Decimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        Decimal { mantissa, scale }
+    }
+
+    /// 对齐两侧的 scale 再比较/相加；两侧 scale 差距过大时，对齐所需的
+    /// `10^diff` 或随后的乘法会超出 `i128`，这里用 `checked_pow`/
+    /// `checked_mul` 探测溢出并报错，而不是 panic 或算出错误的值。
+    fn aligned(self, other: Decimal) -> Result<(i128, i128, u32)> {
+        let overflow = || {
+            Error::invalid_type(format!(
+                "decimal scale overflow aligning {:?} and {:?}",
+                self, other
+            ))
+        };
+        if self.scale == other.scale {
+            Ok((self.mantissa, other.mantissa, self.scale))
+        } else if self.scale > other.scale {
+            let factor = 10i128
+                .checked_pow(self.scale - other.scale)
+                .ok_or_else(overflow)?;
+            let rhs = other.mantissa.checked_mul(factor).ok_or_else(overflow)?;
+            Ok((self.mantissa, rhs, self.scale))
+        } else {
+            let factor = 10i128
+                .checked_pow(other.scale - self.scale)
+                .ok_or_else(overflow)?;
+            let lhs = self.mantissa.checked_mul(factor).ok_or_else(overflow)?;
+            Ok((lhs, other.mantissa, other.scale))
+        }
+    }
+
+    /// `aligned` 溢出时退回到有损的浮点近似值，仅用于无法精确对齐时的
+    /// 比较，永不 panic。
+    fn approx(self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.scale as i32)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        match self.aligned(*other) {
+            Ok((lhs, rhs, _)) => lhs == rhs,
+            Err(_) => false,
+        }
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self.aligned(*other) {
+            Ok((lhs, rhs, _)) => lhs.cmp(&rhs),
+            Err(_) => self
+                .approx()
+                .partial_cmp(&other.approx())
+                .unwrap_or(std::cmp::Ordering::Equal),
+        }
+    }
+}
+
+impl std::ops::Add for Decimal {
+    type Output = Result<Decimal>;
+    fn add(self, other: Decimal) -> Result<Decimal> {
+        let (lhs, rhs, scale) = self.aligned(other)?;
+        Ok(Decimal::new(lhs + rhs, scale))
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.scale == 0 {
+            return write!(f, "{}", self.mantissa);
+        }
+        let negative = self.mantissa < 0;
+        let scale = self.scale as usize;
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let digits = if digits.len() <= scale {
+            format!("{}{}", "0".repeat(scale - digits.len() + 1), digits)
+        } else {
+            digits
+        };
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        write!(
+            f,
+            "{}{}.{}",
+            if negative { "-" } else { "" },
+            int_part,
+            frac_part
+        )
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let negative = s.starts_with('-');
+        let digits_s = s.strip_prefix('-').unwrap_or(s);
+        let (int_part, frac_part) = match digits_s.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, frac_part),
+            None => (digits_s, ""),
+        };
+        // 连同符号一起解析，这样 `i128::MIN` 这类绝对值超出 `i128` 的尾数也能还原
+        let sign = if negative { "-" } else { "" };
+        let mantissa: i128 = format!("{}{}{}", sign, int_part, frac_part)
+            .parse()
+            .map_err(|_| Error::invalid_type(format!("failed to parse decimal from {:?}", s)))?;
+        Ok(Decimal::new(mantissa, frac_part.len() as u32))
+    }
+}
+
 macro_rules! impl_into_value {
     ($variant:ident : $T:ty) => {
         impl From<$T> for Value {
@@ -58,6 +217,8 @@ impl_into_value!(Integer: i8);
 impl_into_value!(Integer: u32);
 impl_into_value!(Integer: u16);
 impl_into_value!(Integer: u8);
+impl_into_value!(UInteger: u64);
+impl_into_value!(BigInteger: i128);
 impl_into_value!(Number: f64);
 impl_into_value!(Number: f32);
 impl_into_value!(String: &str);
@@ -76,19 +237,98 @@ impl_try_from!(Number: f32, "f32");
 impl_try_from!(Boolean: bool, "bool");
 impl_try_from!(Bytes: Bytes, "bytes");
 
+impl From<u128> for Value {
+    fn from(val: u128) -> Value {
+        Value::BigInteger(val as i128)
+    }
+}
+
+impl From<Decimal> for Value {
+    fn from(val: Decimal) -> Value {
+        Value::Decimal(val)
+    }
+}
+
+/// 宽化的数值读取：`Value::Integer` 在符合范围/非负的前提下，仍然可以
+/// 被当作 `u64`/`i128`/`Decimal` 读出，而不必强制调用方先转换类型。
+impl TryFrom<Value> for u64 {
+    type Error = Error;
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::UInteger(val) => Ok(val),
+            Value::Integer(val) if val >= 0 => Ok(val as u64),
+            other => Err(Error::invalid_type(format!(
+                "failed to parse u64 for {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Value> for i128 {
+    type Error = Error;
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::BigInteger(val) => Ok(val),
+            Value::Integer(val) => Ok(val as i128),
+            Value::UInteger(val) => Ok(val as i128),
+            other => Err(Error::invalid_type(format!(
+                "failed to parse i128 for {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Value> for Decimal {
+    type Error = Error;
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Decimal(val) => Ok(val),
+            Value::Integer(val) => Ok(Decimal::new(val as i128, 0)),
+            Value::UInteger(val) => Ok(Decimal::new(val as i128, 0)),
+            Value::BigInteger(val) => Ok(Decimal::new(val, 0)),
+            other => Err(Error::invalid_type(format!(
+                "failed to parse decimal for {:?}",
+                other
+            ))),
+        }
+    }
+}
+
 impl Into<Value> for () {
     fn into(self) -> Value {
         Value::Nil
     }
 }
 
+/// 让 `Value::Nil` 可以被读作 `None`，使注册函数能用普通的 `Option<T>`
+/// 参数区分"实参缺省/为 NULL"与类型错误：缺省或 `Nil` 得到 `None`，
+/// 其余情况按 `T` 正常做类型校验。
+impl<T> TryFrom<Value> for Option<T>
+where
+    T: TryFrom<Value, Error = Error>,
+{
+    type Error = Error;
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        if value.is_nil() {
+            Ok(None)
+        } else {
+            Ok(Some(value.try_into()?))
+        }
+    }
+}
+
 impl TryFrom<Value> for String {
     type Error = Error;
     fn try_from(value: Value) -> Result<Self, Self::Error> {
         let val = match value {
             Value::String(val) => val,
             Value::Integer(val) => val.to_string(),
+            Value::UInteger(val) => val.to_string(),
+            Value::BigInteger(val) => val.to_string(),
             Value::Number(val) => val.to_string(),
+            Value::Decimal(val) => val.to_string(),
             Value::Boolean(val) => val.to_string(),
             Value::Bytes(val) => String::from_utf8(val)?,
             Value::Nil => "Nil".to_string(),
@@ -103,25 +343,56 @@ impl From<String> for Value {
     }
 }
 
+/// 判断字符串是否是一个纯十进制小数字面量（可带一个前导 `-`，且恰好含一个小数点），
+/// 这类文本会被解析为 `Value::Decimal` 而不是会损失精度的 `Value::Number`。
+fn is_decimal_literal(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if digits.is_empty() {
+        return false;
+    }
+    digits.chars().filter(|c| *c == '.').count() == 1
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// 依次尝试 `i64` -> `u64` -> `i128`，在数值溢出时自动提升到更宽的整型，
+/// 而不是像之前那样直接退化为 `String`。
+fn parse_integer_literal(s: &str) -> Option<Value> {
+    if let Ok(val) = s.parse::<i64>() {
+        return Some(Value::Integer(val));
+    }
+    if let Ok(val) = s.parse::<u64>() {
+        return Some(Value::UInteger(val));
+    }
+    if let Ok(val) = s.parse::<i128>() {
+        return Some(Value::BigInteger(val));
+    }
+    None
+}
+
 impl FromStr for Value {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = if s.contains("false") || s.contains("true") {
-            s.parse::<bool>()
-                .map(|v| Value::Boolean(v))
+        // 布尔值与空值用去除首尾空白后的精确匹配判定，而不是子串包含，
+        // 否则 "falsely"、"nullable" 这类文本会被误判
+        let trimmed = s.trim();
+        let value = if trimmed == "true" {
+            Value::Boolean(true)
+        } else if trimmed == "false" {
+            Value::Boolean(false)
+        } else if is_decimal_literal(s) {
+            s.parse::<Decimal>()
+                .map(Value::Decimal)
                 .unwrap_or(Value::String(s.to_owned()))
         } else if s.contains(".") {
             s.parse::<f64>()
                 .map(|v| Value::Number(v))
                 .unwrap_or(Value::String(s.to_owned()))
-        } else if s == "null" || s == "NULL" || s == "Null" {
+        } else if trimmed == "null" || trimmed == "NULL" || trimmed == "Null" {
             Value::Nil
-        } else if s == "nil" || s == "Nil" {
+        } else if trimmed == "nil" || trimmed == "Nil" {
             Value::Nil
         } else {
-            s.parse::<i64>()
-                .map(|v| Value::Integer(v))
-                .unwrap_or(Value::String(s.to_owned()))
+            parse_integer_literal(s).unwrap_or(Value::String(s.to_owned()))
         };
 
         Ok(value)
@@ -133,7 +404,10 @@ impl Display for Value {
         match &self {
             Value::String(val) => write!(f, "{}", val),
             Value::Integer(val) => write!(f, "{}", val),
+            Value::UInteger(val) => write!(f, "{}", val),
+            Value::BigInteger(val) => write!(f, "{}", val),
             Value::Number(val) => write!(f, "{}", val),
+            Value::Decimal(val) => write!(f, "{}", val),
             Value::Boolean(val) => write!(f, "{}", val),
             Value::Bytes(val) => write!(f, "{:?}", val),
             Value::Nil => write!(f, "Nil"),
@@ -146,7 +420,10 @@ impl Value {
         match self {
             Value::String(_) => DataType::String,
             Value::Integer(_) => DataType::Integer,
+            Value::UInteger(_) => DataType::UInteger,
+            Value::BigInteger(_) => DataType::BigInteger,
             Value::Number(_) => DataType::Number,
+            Value::Decimal(_) => DataType::Decimal,
             Value::Boolean(_) => DataType::Boolean,
             Value::Bytes(_) => DataType::Bytes,
             Value::Nil => DataType::Nil,
@@ -160,6 +437,359 @@ impl Value {
             return false;
         }
     }
+
+    /// 按照已知的列类型严格解析文本，不满足目标类型时返回
+    /// `Error::invalid_type`，而不是像 `FromStr` 那样退化为 `String`；
+    /// 供已知输出 schema 的数据源替代启发式的 `FromStr` 推断。
+    pub fn parse_as(s: &str, ty: DataType) -> Result<Value> {
+        let trimmed = s.trim();
+        match ty {
+            DataType::Nil => match trimmed {
+                "nil" | "Nil" | "null" | "NULL" | "Null" => Ok(Value::Nil),
+                _ => Err(Error::invalid_type(format!(
+                    "failed to parse nil for {:?}",
+                    s
+                ))),
+            },
+            DataType::Boolean => match trimmed {
+                "true" => Ok(Value::Boolean(true)),
+                "false" => Ok(Value::Boolean(false)),
+                _ => Err(Error::invalid_type(format!(
+                    "failed to parse boolean for {:?}",
+                    s
+                ))),
+            },
+            DataType::Integer => trimmed.parse::<i64>().map(Value::Integer).map_err(|_| {
+                Error::invalid_type(format!("failed to parse integer for {:?}", s))
+            }),
+            DataType::UInteger => trimmed.parse::<u64>().map(Value::UInteger).map_err(|_| {
+                Error::invalid_type(format!("failed to parse uinteger for {:?}", s))
+            }),
+            DataType::BigInteger => trimmed.parse::<i128>().map(Value::BigInteger).map_err(|_| {
+                Error::invalid_type(format!("failed to parse biginteger for {:?}", s))
+            }),
+            DataType::Number => trimmed.parse::<f64>().map(Value::Number).map_err(|_| {
+                Error::invalid_type(format!("failed to parse number for {:?}", s))
+            }),
+            DataType::Decimal => trimmed.parse::<Decimal>().map(Value::Decimal).map_err(|_| {
+                Error::invalid_type(format!("failed to parse decimal for {:?}", s))
+            }),
+            DataType::Bytes => decode_hex(trimmed).map(Value::Bytes),
+            DataType::String => Ok(Value::String(s.to_string())),
+        }
+    }
+
+    /// 将值编码为自描述的二进制格式：一个字节的类型标签后跟类型相关的负载。
+    /// 标签含义：0=Nil，1=Boolean(1 字节)，2=Integer(8 字节 LE)，
+    /// 3=Number(8 字节 IEEE-754)，4=String(varint 长度 + UTF-8)，
+    /// 5=Bytes(varint 长度 + 原始字节)，6=UInteger(8 字节 LE)，
+    /// 7=BigInteger(16 字节 LE)，8=Decimal(16 字节 LE mantissa + 4 字节 LE scale)。
+    /// 多个编码结果可以首尾相连，再通过 `from_bytes` 依次解析出来，用于
+    /// 节点间传输查询结果。
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Value::Nil => buf.push(0),
+            Value::Boolean(val) => {
+                buf.push(1);
+                buf.push(if *val { 1 } else { 0 });
+            }
+            Value::Integer(val) => {
+                buf.push(2);
+                buf.extend_from_slice(&val.to_le_bytes());
+            }
+            Value::Number(val) => {
+                buf.push(3);
+                buf.extend_from_slice(&val.to_le_bytes());
+            }
+            Value::String(val) => {
+                buf.push(4);
+                encode_varint(val.len() as u64, &mut buf);
+                buf.extend_from_slice(val.as_bytes());
+            }
+            Value::Bytes(val) => {
+                buf.push(5);
+                encode_varint(val.len() as u64, &mut buf);
+                buf.extend_from_slice(val);
+            }
+            Value::UInteger(val) => {
+                buf.push(6);
+                buf.extend_from_slice(&val.to_le_bytes());
+            }
+            Value::BigInteger(val) => {
+                buf.push(7);
+                buf.extend_from_slice(&val.to_le_bytes());
+            }
+            Value::Decimal(val) => {
+                buf.push(8);
+                buf.extend_from_slice(&val.mantissa.to_le_bytes());
+                buf.extend_from_slice(&val.scale.to_le_bytes());
+            }
+        }
+        buf
+    }
+
+    /// 从二进制缓冲区中解析出一个值，返回解析出的值及消耗的字节数，
+    /// 以便在同一个缓冲区里连续解析出一串值。
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Value, usize)> {
+        let tag = *bytes
+            .get(0)
+            .ok_or_else(|| Error::invalid_type("unexpected end of input while reading tag".to_string()))?;
+        match tag {
+            0 => Ok((Value::Nil, 1)),
+            1 => {
+                let val = *bytes.get(1).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading boolean".to_string())
+                })?;
+                Ok((Value::Boolean(val != 0), 2))
+            }
+            2 => {
+                let raw = bytes.get(1..9).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading integer".to_string())
+                })?;
+                let mut octets = [0u8; 8];
+                octets.copy_from_slice(raw);
+                Ok((Value::Integer(i64::from_le_bytes(octets)), 9))
+            }
+            3 => {
+                let raw = bytes.get(1..9).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading number".to_string())
+                })?;
+                let mut octets = [0u8; 8];
+                octets.copy_from_slice(raw);
+                Ok((Value::Number(f64::from_le_bytes(octets)), 9))
+            }
+            4 => {
+                let (len, len_size) = decode_varint(&bytes[1..])?;
+                let start = 1 + len_size;
+                let end = checked_end(start, len)?;
+                let raw = bytes.get(start..end).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading string".to_string())
+                })?;
+                Ok((Value::String(String::from_utf8(raw.to_vec())?), end))
+            }
+            5 => {
+                let (len, len_size) = decode_varint(&bytes[1..])?;
+                let start = 1 + len_size;
+                let end = checked_end(start, len)?;
+                let raw = bytes.get(start..end).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading bytes".to_string())
+                })?;
+                Ok((Value::Bytes(raw.to_vec()), end))
+            }
+            6 => {
+                let raw = bytes.get(1..9).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading uinteger".to_string())
+                })?;
+                let mut octets = [0u8; 8];
+                octets.copy_from_slice(raw);
+                Ok((Value::UInteger(u64::from_le_bytes(octets)), 9))
+            }
+            7 => {
+                let raw = bytes.get(1..17).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading biginteger".to_string())
+                })?;
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(raw);
+                Ok((Value::BigInteger(i128::from_le_bytes(octets)), 17))
+            }
+            8 => {
+                let mantissa_raw = bytes.get(1..17).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading decimal".to_string())
+                })?;
+                let mut mantissa_octets = [0u8; 16];
+                mantissa_octets.copy_from_slice(mantissa_raw);
+                let scale_raw = bytes.get(17..21).ok_or_else(|| {
+                    Error::invalid_type("unexpected end of input while reading decimal".to_string())
+                })?;
+                let mut scale_octets = [0u8; 4];
+                scale_octets.copy_from_slice(scale_raw);
+                Ok((
+                    Value::Decimal(Decimal::new(
+                        i128::from_le_bytes(mantissa_octets),
+                        u32::from_le_bytes(scale_octets),
+                    )),
+                    21,
+                ))
+            }
+            _ => Err(Error::invalid_type(format!("unknown value tag {}", tag))),
+        }
+    }
+
+    /// 将值写成规范化的文本形式：字符串加引号转义，`Bytes` 写成
+    /// `#b"<hex>"`，`Nil` 写成 `#nil`。与 `Display` 不同，这种形式是
+    /// 无歧义的，总能通过 `parse_canonical` 还原出原始值。
+    pub fn to_canonical_string(&self) -> String {
+        match self {
+            Value::Nil => "#nil".to_string(),
+            Value::Boolean(val) => val.to_string(),
+            Value::Integer(val) => val.to_string(),
+            Value::UInteger(val) => format!("{}u", val),
+            Value::BigInteger(val) => format!("{}n", val),
+            Value::Number(val) => format!("{:?}", val),
+            Value::Decimal(val) => format!("{}m", val),
+            Value::String(val) => format!("\"{}\"", escape_string(val)),
+            Value::Bytes(val) => format!("#b\"{}\"", encode_hex(val)),
+        }
+    }
+
+    /// 解析 `to_canonical_string` 产生的文本，无法识别的形式返回
+    /// `Error::invalid_type`。
+    pub fn parse_canonical(s: &str) -> Result<Value> {
+        if s == "#nil" {
+            return Ok(Value::Nil);
+        }
+        if let Some(hex) = s.strip_prefix("#b\"").and_then(|rest| rest.strip_suffix('"')) {
+            return Ok(Value::Bytes(decode_hex(hex)?));
+        }
+        if let Some(inner) = s.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+            return Ok(Value::String(unescape_string(inner)?));
+        }
+        if s == "true" {
+            return Ok(Value::Boolean(true));
+        }
+        if s == "false" {
+            return Ok(Value::Boolean(false));
+        }
+        if let Some(digits) = s.strip_suffix('u') {
+            return digits.parse::<u64>().map(Value::UInteger).map_err(|_| {
+                Error::invalid_type(format!("failed to parse canonical u64 from {:?}", s))
+            });
+        }
+        if let Some(digits) = s.strip_suffix('n') {
+            return digits.parse::<i128>().map(Value::BigInteger).map_err(|_| {
+                Error::invalid_type(format!("failed to parse canonical i128 from {:?}", s))
+            });
+        }
+        if let Some(digits) = s.strip_suffix('m') {
+            return digits.parse::<Decimal>().map(Value::Decimal).map_err(|_| {
+                Error::invalid_type(format!("failed to parse canonical decimal from {:?}", s))
+            });
+        }
+        if let Ok(val) = s.parse::<i64>() {
+            return Ok(Value::Integer(val));
+        }
+        if let Ok(val) = s.parse::<f64>() {
+            return Ok(Value::Number(val));
+        }
+        Err(Error::invalid_type(format!(
+            "failed to parse canonical value from {:?}",
+            s
+        )))
+    }
+}
+
+fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        buf.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// 一个 `u64` 最多需要 10 个字节的 varint 编码（`ceil(64 / 7)`）；
+/// 超出这个长度说明缓冲区被截断或损坏，直接报错而不是继续移位，
+/// 否则 `shift` 会超过 63 导致移位溢出 panic。
+const MAX_VARINT_BYTES: usize = 10;
+
+fn decode_varint(bytes: &[u8]) -> Result<(u64, usize)> {
+    let mut value: u64 = 0;
+    let mut shift = 0;
+    for (i, byte) in bytes.iter().enumerate() {
+        if i >= MAX_VARINT_BYTES {
+            return Err(Error::invalid_type(
+                "varint exceeds the maximum of 10 bytes for a u64 length".to_string(),
+            ));
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+        shift += 7;
+    }
+    Err(Error::invalid_type(
+        "unexpected end of input while reading length".to_string(),
+    ))
+}
+
+/// 一个合法的 varint 编码长度解码出来后仍可能是 `u64::MAX` 这种不可能
+/// 由真实负载撑起的值，`start + len` 会溢出 `usize` 并 panic；这里改用
+/// `checked_add` 返回 `Error::invalid_type`，交由调用方的 `bytes.get`
+/// 去判断长度是否真的越界。
+fn checked_end(start: usize, len: u64) -> Result<usize> {
+    usize::try_from(len)
+        .ok()
+        .and_then(|len| start.checked_add(len))
+        .ok_or_else(|| {
+            Error::invalid_type("length prefix overflows while reading value".to_string())
+        })
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        s.push_str(&format!("{:02x}", byte));
+    }
+    s
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return Err(Error::invalid_type(format!("invalid hex string {:?}", s)));
+    }
+    let chars: Vec<char> = s.chars().collect();
+    let mut bytes = Vec::with_capacity(chars.len() / 2);
+    for pair in chars.chunks(2) {
+        let byte = u8::from_str_radix(&pair.iter().collect::<String>(), 16)
+            .map_err(|_| Error::invalid_type(format!("invalid hex string {:?}", s)))?;
+        bytes.push(byte);
+    }
+    Ok(bytes)
+}
+
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape_string(s: &str) -> Result<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                return Err(Error::invalid_type(format!(
+                    "invalid escape sequence \\{}",
+                    other
+                )))
+            }
+            None => {
+                return Err(Error::invalid_type(
+                    "unterminated escape sequence".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(out)
 }
 
 #[test]
@@ -174,7 +804,21 @@ fn from_test() {
     assert_eq!(Value::Boolean(true), val);
 
     let val = "10.01".parse::<Value>().unwrap();
-    assert_eq!(Value::Number(10.01), val);
+    assert_eq!(Value::Decimal(Decimal::new(1001, 2)), val);
+
+    let val = "-10.01".parse::<Value>().unwrap();
+    assert_eq!(Value::Decimal(Decimal::new(-1001, 2)), val);
+
+    let val = "1.0e1".parse::<Value>().unwrap();
+    assert_eq!(Value::Number(10.0), val);
+
+    let val = u64::MAX.to_string().parse::<Value>().unwrap();
+    assert_eq!(Value::UInteger(u64::MAX), val);
+
+    let val = "170141183460469231731687303715884105727"
+        .parse::<Value>()
+        .unwrap();
+    assert_eq!(Value::BigInteger(i128::MAX), val);
 
     let val = "10._".parse::<Value>().unwrap();
     assert_eq!(Value::String("10._".to_string()), val);
@@ -196,6 +840,39 @@ fn from_test() {
 
     let val = "null".parse::<Value>().unwrap();
     assert_eq!(Value::Nil, val);
+
+    let val = "falsely".parse::<Value>().unwrap();
+    assert_eq!(Value::String("falsely".to_string()), val);
+
+    let val = " true ".parse::<Value>().unwrap();
+    assert_eq!(Value::Boolean(true), val);
+}
+
+#[test]
+fn parse_as_test() {
+    assert_eq!(
+        Value::Integer(42),
+        Value::parse_as("42", DataType::Integer).unwrap()
+    );
+    assert_eq!(
+        Value::Boolean(true),
+        Value::parse_as("true", DataType::Boolean).unwrap()
+    );
+    assert_eq!(
+        Value::Nil,
+        Value::parse_as("nil", DataType::Nil).unwrap()
+    );
+    assert_eq!(
+        Value::Decimal(Decimal::new(1234, 2)),
+        Value::parse_as("12.34", DataType::Decimal).unwrap()
+    );
+    assert_eq!(
+        Value::String("v1.2.3".to_string()),
+        Value::parse_as("v1.2.3", DataType::String).unwrap()
+    );
+
+    assert!(Value::parse_as("not-a-bool", DataType::Boolean).is_err());
+    assert!(Value::parse_as("12.5", DataType::Integer).is_err());
 }
 
 #[test]
@@ -218,3 +895,124 @@ fn from_value() {
     let val: Value = vec![0x09_u8, 0x12].into();
     assert_eq!(Value::Bytes(vec![0x09_u8, 0x12]), val);
 }
+
+#[test]
+fn binary_roundtrip_test() {
+    let values = vec![
+        Value::Nil,
+        Value::Boolean(true),
+        Value::Boolean(false),
+        Value::Integer(-42),
+        Value::Number(3.25),
+        Value::String("hello".to_string()),
+        Value::Bytes(vec![0x09, 0x12]),
+        Value::UInteger(u64::MAX),
+        Value::BigInteger(i128::MIN),
+        Value::Decimal(Decimal::new(-1001, 2)),
+    ];
+
+    for value in values {
+        let bytes = value.to_bytes();
+        let (decoded, consumed) = Value::from_bytes(&bytes).unwrap();
+        assert_eq!(value, decoded);
+        assert_eq!(consumed, bytes.len());
+    }
+}
+
+#[test]
+fn binary_stream_test() {
+    let mut buf = Vec::new();
+    buf.extend(Value::Integer(1).to_bytes());
+    buf.extend(Value::String("ok".to_string()).to_bytes());
+
+    let (first, used) = Value::from_bytes(&buf).unwrap();
+    assert_eq!(Value::Integer(1), first);
+
+    let (second, _) = Value::from_bytes(&buf[used..]).unwrap();
+    assert_eq!(Value::String("ok".to_string()), second);
+}
+
+#[test]
+fn from_bytes_rejects_oversized_varint_test() {
+    // 标签 4（String）后面跟着 11 个都设置了延续位的字节：一个合法的
+    // u64 长度最多只需要 10 个字节，这应该返回错误而不是移位溢出 panic。
+    let mut buf = vec![4u8];
+    buf.extend(std::iter::repeat(0x80u8).take(11));
+    assert!(Value::from_bytes(&buf).is_err());
+}
+
+#[test]
+fn from_bytes_rejects_oversized_length_test() {
+    // 标签 4（String）后面跟着一个合法的 10 字节 varint，解码出
+    // `u64::MAX`：`start + len` 会溢出 `usize`，这应该返回错误而不是 panic。
+    let mut buf = vec![4u8];
+    buf.extend([0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+    assert!(Value::from_bytes(&buf).is_err());
+}
+
+#[test]
+fn canonical_text_roundtrip_test() {
+    let values = vec![
+        Value::Nil,
+        Value::Boolean(true),
+        Value::Integer(-7),
+        Value::Number(1.5),
+        Value::String("he said \"hi\"".to_string()),
+        Value::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+        Value::UInteger(u64::MAX),
+        Value::BigInteger(i128::MIN),
+        Value::Decimal(Decimal::new(-1001, 2)),
+    ];
+
+    for value in values {
+        let text = value.to_canonical_string();
+        let decoded = Value::parse_canonical(&text).unwrap();
+        assert_eq!(value, decoded);
+    }
+}
+
+#[test]
+fn decimal_exact_add_and_compare_test() {
+    let a = Decimal::new(150, 2); // 1.50
+    let b = Decimal::new(5, 1); // 0.5
+    assert_eq!(Decimal::new(200, 2), (a + b).unwrap());
+    assert!(a > b);
+    assert_eq!(a, Decimal::new(15, 1));
+}
+
+#[test]
+fn decimal_large_scale_diff_does_not_panic_test() {
+    // scale 差达到 39 时，对齐所需的 10^39 超出 i128，之前会直接 panic。
+    let a = Decimal::new(1, 39);
+    let b = Decimal::new(1, 0);
+    assert!(a < b);
+    assert!(a != b);
+    assert!((a + b).is_err());
+}
+
+#[test]
+fn widening_try_from_test() {
+    let val: u64 = Value::Integer(10).try_into().unwrap();
+    assert_eq!(10u64, val);
+
+    let val: i128 = Value::UInteger(42).try_into().unwrap();
+    assert_eq!(42i128, val);
+
+    let val: Decimal = Value::Integer(7).try_into().unwrap();
+    assert_eq!(Decimal::new(7, 0), val);
+
+    let err: Result<u64> = Value::Integer(-1).try_into();
+    assert!(err.is_err());
+}
+
+#[test]
+fn optional_try_from_test() {
+    let val: Option<i64> = Value::Nil.try_into().unwrap();
+    assert_eq!(None, val);
+
+    let val: Option<i64> = Value::Integer(3).try_into().unwrap();
+    assert_eq!(Some(3), val);
+
+    let err: Result<Option<i64>> = Value::String("oops".to_string()).try_into();
+    assert!(err.is_err());
+}